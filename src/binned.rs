@@ -19,6 +19,8 @@ pub struct BinnedWaveformRenderer<T: Sample> {
     sample_rate: f64,
     bin_size: usize,
     minmax: MinMaxPairSequence<T>,
+    antialias: bool,
+    gradient: Option<Vec<Vec<u8>>>,
 }
 
 impl<T: Sample> BinnedWaveformRenderer<T> {
@@ -70,6 +72,8 @@ impl<T: Sample> BinnedWaveformRenderer<T> {
             bin_size: bin_size,
             minmax: minmax,
             sample_rate: samples.sample_rate,
+            antialias: false,
+            gradient: None,
         })
     }
 
@@ -167,6 +171,10 @@ impl<T: Sample> BinnedWaveformRenderer<T> {
         let bins_per_pixel_floor = bins_per_pixel.floor() as usize;
         let bins_per_pixel_ceil = bins_per_pixel.ceil() as usize;
 
+        // Only used by the gradient branch below; hoisted out of the
+        // column loop so it isn't re-allocated on every column.
+        let gradient_bg_bytes = color_bytes(self.config.get_background());
+
         let offset_bin_idx = begin / self.bin_size;
         let mut start_bin_idx = offset_bin_idx;
         for x in 0..w {
@@ -220,6 +228,48 @@ impl<T: Sample> BinnedWaveformRenderer<T> {
                     ),
                 ) as usize;
 
+            if let Some(lut) = &self.gradient {
+                let peak = (min.into()).abs().max((max.into()).abs());
+                let level = gradient_level(peak, self.config.amp_max);
+                let fg_bytes = &lut[level];
+
+                if self.antialias {
+                    let min_translated_f = (h as f64) - clamp_to_height((min.into() - self.config.amp_min) * scale, h);
+                    let max_translated_f = (h as f64) - clamp_to_height((max.into() - self.config.amp_min) * scale, h);
+                    render_column_antialiased(img, fullw, x, offx, offy, h, max_translated_f, min_translated_f, &gradient_bg_bytes, fg_bytes);
+                } else {
+                    render_column_solid(img, fullw, x, offx, offy, h, max_translated, min_translated, &gradient_bg_bytes, fg_bytes);
+                }
+
+                continue;
+            }
+
+            if self.antialias {
+                // The fractional, unfloored positions of the bar's edges.
+                // These are what let us blend the two boundary rows instead
+                // of hard-quantizing them to the nearest pixel row.
+                let min_translated_f = (h as f64) - clamp_to_height((min.into() - self.config.amp_min) * scale, h);
+                let max_translated_f = (h as f64) - clamp_to_height((max.into() - self.config.amp_min) * scale, h);
+
+                match (self.config.get_background(), self.config.get_foreground()) {
+                    (Color::Scalar(ba), Color::Scalar(fa)) => {
+                        render_column_antialiased(img, fullw, x, offx, offy, h, max_translated_f, min_translated_f, &[ba], &[fa]);
+                    },
+                    (Color::Vector3 (br, bg, bb), Color::Vector3 (fr, fg, fb)) => {
+                        render_column_antialiased(img, fullw, x, offx, offy, h, max_translated_f, min_translated_f, &[br, bg, bb], &[fr, fg, fb]);
+                    },
+                    (Color::Vector4 (br, bg, bb, ba), Color::Vector4 (fr, fg, fb, fa)) => {
+                        render_column_antialiased(img, fullw, x, offx, offy, h, max_translated_f, min_translated_f, &[br, bg, bb, ba], &[fr, fg, fb, fa]);
+                    },
+                    // This case is unreachable because inconsistent
+                    // `Color` formats are checked whenever a user
+                    // creates a `WaveformConfig`.
+                    (_, _) => unreachable!(),
+                }
+
+                continue;
+            }
+
             // Putting this `match` outside for loops improved the speed.
             match (self.config.get_background(), self.config.get_foreground()) {
                 (Color::Scalar(ba), Color::Scalar(fa)) => {
@@ -346,7 +396,7 @@ impl<T: Sample> BinnedWaveformRenderer<T> {
                 // This case is unreachable because inconsistent
                 // `Color` formats are checked whenever a user
                 // creates a `WaveformConfig`.
-                (_, _) => unreachable!(), 
+                (_, _) => unreachable!(),
             }
         }
 
@@ -359,11 +409,417 @@ impl<T: Sample> BinnedWaveformRenderer<T> {
     pub fn get_sample_rate(&self) -> f64 {
         self.sample_rate
     }
+
+    /// Whether the top/bottom edges of the min/max bar are anti-aliased.
+    ///
+    /// See `set_antialiasing`.
+    pub fn get_antialiasing(&self) -> bool {
+        self.antialias
+    }
+
+    /// Enables or disables anti-aliasing of the bar's boundary rows.
+    ///
+    /// When enabled, `render_write` computes the min/max edges as
+    /// fractional pixel positions and blends the foreground into the
+    /// background on the two boundary rows instead of hard-quantizing
+    /// them to the nearest integer row. This is slower than the default
+    /// fast integer path, so it is off by default.
+    pub fn set_antialiasing(&mut self, enabled: bool) {
+        self.antialias = enabled;
+    }
+
+    /// Replaces the flat foreground color with a 256-entry gradient keyed
+    /// on a column's peak amplitude, turning the bars into a heatmap
+    /// (e.g. quiet = blue, loud = red) instead of a single flat color.
+    ///
+    /// `stops` must contain at least two colors, all of the same
+    /// `Color` variant as `self.config`'s background/foreground (i.e. all
+    /// `Scalar`, all `Vector3`, or all `Vector4`). The stops are spread
+    /// evenly across the 0..256 amplitude levels and linearly
+    /// interpolated between, so the lookup table itself only needs to be
+    /// built once, keeping the inner render loop branch-free.
+    pub fn set_gradient(&mut self, stops: &[Color]) -> Result<(), Box<dyn Error>> {
+        let fg_channels = color_bytes(self.config.get_foreground()).len();
+        if stops.iter().any(|s| color_bytes(*s).len() != fg_channels) {
+            return Err(Box::new(InvalidSizeError { var_name: "stops".to_string() }));
+        }
+
+        self.gradient = Some(build_gradient_lut(stops)?);
+        Ok(())
+    }
+
+    /// Reverts to the flat foreground color set in `self.config`.
+    pub fn clear_gradient(&mut self) {
+        self.gradient = None;
+    }
+}
+
+/// Extracts a `Color`'s channel bytes, in the same order used everywhere
+/// else in this module (`bg_colors`/`fg_colors` array literals).
+fn color_bytes(c: Color) -> Vec<u8> {
+    match c {
+        Color::Scalar(a) => vec![a],
+        Color::Vector3(r, g, b) => vec![r, g, b],
+        Color::Vector4(r, g, b, a) => vec![r, g, b, a],
+    }
+}
+
+/// Like `color_bytes`, but returns the channel bytes in fixed-size stack
+/// storage instead of a heap-allocated `Vec`, along with how many of the
+/// 4 slots are actually in use. `BinnedWaveformRendererRef::render_write`
+/// uses this so the allocation-free `ref-renderer` path never touches an
+/// allocator.
+#[cfg(feature = "ref-renderer")]
+fn color_bytes_arr(c: Color) -> ([u8; 4], usize) {
+    match c {
+        Color::Scalar(a) => ([a, 0, 0, 0], 1),
+        Color::Vector3(r, g, b) => ([r, g, b, 0], 3),
+        Color::Vector4(r, g, b, a) => ([r, g, b, a], 4),
+    }
+}
+
+/// Builds a 256-entry color lookup table by evenly spacing `stops` across
+/// the 0..256 amplitude levels and linearly interpolating between them.
+fn build_gradient_lut(stops: &[Color]) -> Result<Vec<Vec<u8>>, Box<dyn Error>> {
+    if stops.len() < 2 {
+        return Err(Box::new(InvalidSizeError { var_name: "stops".to_string() }));
+    }
+
+    let channels = color_bytes(stops[0]).len();
+    for s in stops {
+        if color_bytes(*s).len() != channels {
+            return Err(Box::new(InvalidSizeError { var_name: "stops".to_string() }));
+        }
+    }
+
+    let stop_bytes: Vec<Vec<u8>> = stops.iter().map(|s| color_bytes(*s)).collect();
+
+    let mut lut = Vec::with_capacity(256);
+    for level in 0..256usize {
+        let t = (level as f64) / 255f64 * ((stop_bytes.len() - 1) as f64);
+        let i0 = t.floor() as usize;
+        let i1 = cmp::min(i0 + 1, stop_bytes.len() - 1);
+        let frac = t - (i0 as f64);
+
+        let entry: Vec<u8> = (0..channels)
+            .map(|c| {
+                let a = f64::from(stop_bytes[i0][c]);
+                let b = f64::from(stop_bytes[i1][c]);
+                (a + (b - a) * frac).round() as u8
+            })
+            .collect();
+        lut.push(entry);
+    }
+
+    Ok(lut)
+}
+
+/// Maps a column's peak amplitude (the larger-magnitude of its min/max)
+/// into a 0..255 gradient level; peaks above `amp_max` clamp to 255. Unlike
+/// the `amp_min..amp_max` scale `render_write` uses to position the bar,
+/// silence (`peak == 0`) must land at level 0 for the "quiet = blue,
+/// loud = red" heatmap to actually hold, so this is normalized over
+/// `0..amp_max` instead.
+fn gradient_level(peak: f64, amp_max: f64) -> usize {
+    let t = peak / amp_max;
+    let level = (t * 255f64).round();
+    if level < 0f64 {
+        0
+    } else if level > 255f64 {
+        255
+    } else {
+        level as usize
+    }
+}
+
+/// Fills a column's three segments (background, foreground, background)
+/// using plain indexing rather than the `pixel!`/`flipping_three_segment_for`
+/// macros, since the foreground bytes here come from a gradient lookup
+/// table rather than a single `match`-able `Color`.
+fn render_column_solid(
+    img: &mut [u8],
+    fullw: usize,
+    x: usize,
+    offx: usize,
+    offy: usize,
+    h: usize,
+    max_translated: usize,
+    min_translated: usize,
+    bg: &[u8],
+    fg: &[u8],
+) {
+    let bpp = bg.len();
+    let put = |img: &mut [u8], y: usize, bytes: &[u8]| {
+        let base = ((offy + y) * fullw + (offx + x)) * bpp;
+        img[base..base + bpp].copy_from_slice(bytes);
+    };
+
+    for y in 0..cmp::min(max_translated, h) {
+        put(img, y, bg);
+    }
+    for y in max_translated..cmp::min(min_translated, h) {
+        put(img, y, fg);
+    }
+    for y in cmp::max(min_translated, max_translated)..h {
+        put(img, y, bg);
+    }
+}
+
+/// Clamps `v` to `0.0..=height` without rounding, as a prelude to flooring.
+fn clamp_to_height(v: f64, height: usize) -> f64 {
+    if v < 0f64 {
+        0f64
+    } else if v > height as f64 {
+        height as f64
+    } else {
+        v
+    }
+}
+
+/// Blends `new` into `*prev` by a coverage amount `a` out of 256.
+fn blend(prev: &mut u8, new: u8, a: u64) {
+    if new > *prev {
+        *prev += (u64::from(new - *prev) * a / 256) as u8
+    } else {
+        *prev -= (u64::from(*prev - new) * a / 256) as u8
+    }
+}
+
+/// Anti-aliased equivalent of the `flipping_three_segment_for` fast path.
+///
+/// `max_translated_f` / `min_translated_f` are the bar's top/bottom edges
+/// as fractional pixel-row positions (already clamped to `0.0..=h`). The
+/// rows they fall between are filled solid; the two rows they fall on are
+/// blended by how much of that row the bar actually covers.
+fn render_column_antialiased(
+    img: &mut [u8],
+    fullw: usize,
+    x: usize,
+    offx: usize,
+    offy: usize,
+    h: usize,
+    max_translated_f: f64,
+    min_translated_f: f64,
+    bg: &[u8],
+    fg: &[u8],
+) {
+    let bpp = bg.len();
+
+    let put = |img: &mut [u8], y: usize, bytes: &[u8]| {
+        let base = ((offy + y) * fullw + (offx + x)) * bpp;
+        img[base..base + bpp].copy_from_slice(bytes);
+    };
+    let blend_row = |img: &mut [u8], y: usize, frac: f64| {
+        let base = ((offy + y) * fullw + (offx + x)) * bpp;
+        let a = (frac.max(0f64).min(1f64) * 256f64) as u64;
+        for c in 0..bpp {
+            img[base + c] = bg[c];
+            blend(&mut img[base + c], fg[c], a);
+        }
+    };
+
+    let max_edge = max_translated_f.floor() as usize;
+    let min_edge = min_translated_f.floor() as usize;
+
+    for y in 0..cmp::min(max_edge, h) {
+        put(img, y, bg);
+    }
+
+    if max_edge == min_edge {
+        if max_edge < h {
+            blend_row(img, max_edge, min_translated_f - max_translated_f);
+        }
+    } else {
+        if max_edge < h {
+            blend_row(img, max_edge, 1f64 - max_translated_f.fract());
+        }
+        for y in (max_edge + 1)..cmp::min(min_edge, h) {
+            put(img, y, fg);
+        }
+        if min_edge < h {
+            blend_row(img, min_edge, min_translated_f.fract());
+        }
+    }
+
+    for y in cmp::max(min_edge + 1, max_edge + 1)..h {
+        put(img, y, bg);
+    }
+}
+
+/// A rendering error that carries no heap-allocated state, for callers of
+/// `BinnedWaveformRendererRef` who want to avoid `Box<dyn Error>`.
+#[cfg(feature = "ref-renderer")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RenderError {
+    /// A requested size (a `shape`, an `offsets`/`shape` pair against a
+    /// destination buffer, or a caller-supplied bin slice) was invalid.
+    InvalidSize,
+}
+
+/// An allocation-free counterpart to `BinnedWaveformRenderer`.
+///
+/// `BinnedWaveformRenderer` always owns its binned min/max data in a
+/// heap-allocated `Vec` (via `MinMaxPairSequence`) and reports errors as
+/// `Box<dyn Error>`. This renderer instead borrows a caller-supplied
+/// `&[MinMaxPair<T>]` — binned ahead of time into storage the caller owns,
+/// e.g. a static array or a bump allocator — and reports errors as the
+/// allocation-free `RenderError`, so its own `render_write` never touches
+/// an allocator. Its `render_write` is otherwise the same column math as
+/// `BinnedWaveformRenderer::render_write`, reusing the same
+/// `render_column_solid` / `render_column_antialiased` helpers; it always
+/// goes through these slice-based helpers with a flat `Color`, rather
+/// than the `std` renderer's per-`Color`-variant fast match, trading a
+/// little speed for a much smaller, allocation-free code path.
+///
+/// This does *not* make the crate buildable on `no_std` targets — the
+/// crate as a whole still links `std` unconditionally (`binned.rs`'s
+/// `Box<dyn Error>` / `std::io::Write` usage, `png`, `gif`). What this
+/// feature buys a constrained caller is an allocation-free rendering
+/// *path* they can use from an otherwise-`std` build, e.g. to avoid
+/// per-frame allocation in a hot loop; it is not a microcontroller target.
+#[cfg(feature = "ref-renderer")]
+pub struct BinnedWaveformRendererRef<'a, T: Sample> {
+    pub config: WaveformConfig,
+    sample_rate: f64,
+    bin_size: usize,
+    minmax: &'a [MinMaxPair<T>],
+    antialias: bool,
+}
+
+#[cfg(feature = "ref-renderer")]
+impl<'a, T: Sample> BinnedWaveformRendererRef<'a, T> {
+    /// The constructor.
+    ///
+    /// Unlike `BinnedWaveformRenderer::new`, `bins` must already hold the
+    /// per-bin min/max pairs — there is no sample buffer to bin here,
+    /// since binning into caller-owned storage without a global allocator
+    /// is left to the caller.
+    pub fn new(bins: &'a [MinMaxPair<T>], bin_size: usize, sample_rate: f64, config: WaveformConfig) -> Result<Self, RenderError> {
+        if bins.is_empty() || bin_size == 0 {
+            return Err(RenderError::InvalidSize);
+        }
+
+        Ok(BinnedWaveformRendererRef {
+            config: config,
+            sample_rate: sample_rate,
+            bin_size: bin_size,
+            minmax: bins,
+            antialias: false,
+        })
+    }
+
+    pub fn get_bin_size(&self) -> usize {
+        self.bin_size
+    }
+    pub fn get_sample_rate(&self) -> f64 {
+        self.sample_rate
+    }
+    pub fn get_antialiasing(&self) -> bool {
+        self.antialias
+    }
+    pub fn set_antialiasing(&mut self, enabled: bool) {
+        self.antialias = enabled;
+    }
+
+    /// Writes the image into a mutable reference to a slice.
+    ///
+    /// See `BinnedWaveformRenderer::render_write` for the argument
+    /// semantics; this mirrors it exactly, just sourced from a borrowed
+    /// `&[MinMaxPair<T>]` instead of an owned `Vec`.
+    pub fn render_write(&self, range: TimeRange, offsets: (usize, usize), shape: (usize, usize), img: &mut [u8], full_shape: (usize, usize)) -> Result<(), RenderError> {
+        let (w, h) = shape;
+        if w == 0 || h == 0 {
+            return Err(RenderError::InvalidSize);
+        }
+
+        let (fullw, fullh) = full_shape;
+        if fullw < w || fullh < h {
+            return Err(RenderError::InvalidSize);
+        }
+        let _ = fullh;
+
+        let (offx, offy) = offsets;
+        let (bg_arr, bg_len) = color_bytes_arr(self.config.get_background());
+        let (fg_arr, fg_len) = color_bytes_arr(self.config.get_foreground());
+        let bg_bytes = &bg_arr[..bg_len];
+        let fg_bytes = &fg_arr[..fg_len];
+        let bpp = bg_len;
+        if (offx + w) * (offy + h) * bpp > img.len() {
+            return Err(RenderError::InvalidSize);
+        }
+
+        let (begin, end) = match range {
+            TimeRange::Seconds(b, e) => (
+                (b * self.sample_rate) as usize,
+                (e * self.sample_rate) as usize,
+            ),
+            TimeRange::Samples(b, e) => (b, e),
+        };
+        let nb_samples = end - begin;
+        let samples_per_pixel = (nb_samples as f64) / (w as f64);
+        let bins_per_pixel = samples_per_pixel / (self.bin_size as f64);
+        let bins_per_pixel_floor = bins_per_pixel.floor() as usize;
+        let bins_per_pixel_ceil = bins_per_pixel.ceil() as usize;
+
+        let offset_bin_idx = begin / self.bin_size;
+        let mut start_bin_idx = offset_bin_idx;
+        for x in 0..w {
+            let inc = if ((start_bin_idx - offset_bin_idx) as f64 + 1f64) / (x as f64) < bins_per_pixel {
+                bins_per_pixel_ceil
+            } else {
+                bins_per_pixel_floor
+            };
+
+            let mut min: T;
+            let mut max: T;
+            if start_bin_idx < self.minmax.len() - 1 {
+                let ref d = self.minmax[start_bin_idx];
+                min = d.min;
+                max = d.max;
+                let range_start = start_bin_idx;
+                let range_end = if start_bin_idx + inc <= self.minmax.len() {
+                    start_bin_idx + inc
+                } else {
+                    self.minmax.len()
+                };
+                for b in self.minmax[range_start..range_end].iter() {
+                    if b.min < min {
+                        min = b.min
+                    }
+                    if b.max > max {
+                        max = b.max
+                    }
+                }
+                start_bin_idx = range_end;
+            } else {
+                min = T::zero();
+                max = T::zero();
+            }
+
+            let scale = 1f64 / (self.config.amp_max - self.config.amp_min) * (h as f64);
+            let min_translated: usize = h -
+                cmp::max(0, cmp::min(h as i32, ((min.into() - self.config.amp_min) * scale).floor() as i32)) as usize;
+            let max_translated: usize = h -
+                cmp::max(0, cmp::min(h as i32, ((max.into() - self.config.amp_min) * scale).floor() as i32)) as usize;
+
+            if self.antialias {
+                let min_translated_f = (h as f64) - clamp_to_height((min.into() - self.config.amp_min) * scale, h);
+                let max_translated_f = (h as f64) - clamp_to_height((max.into() - self.config.amp_min) * scale, h);
+                render_column_antialiased(img, fullw, x, offx, offy, h, max_translated_f, min_translated_f, bg_bytes, fg_bytes);
+            } else {
+                render_column_solid(img, fullw, x, offx, offy, h, max_translated, min_translated, bg_bytes, fg_bytes);
+            }
+        }
+
+        Ok(())
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::BinnedWaveformRenderer;
+    #[cfg(feature = "ref-renderer")]
+    use super::BinnedWaveformRendererRef;
     use ::misc::*;
 
     #[test]
@@ -398,6 +854,102 @@ mod tests {
         assert_eq!(v1, v2);
     }
 
+    #[test]
+    fn antialiasing_blends_boundary_rows() {
+        let tr = TimeRange::Samples(0, 10);
+        let (width, height) = (1, 10);
+        // The single rendered column spans -1 to 0.55. The bottom edge (-1)
+        // lands exactly on the image's bottom edge, but the top edge (0.55)
+        // maps to 7.75 of the 10 rows, so row 2 is only partly covered and
+        // must be blended rather than landing on an exact pixel row.
+        let samples: Vec<f64> = vec![-1f64, -1f64, -1f64, -1f64, -1f64, 0.55f64, 0.55f64, 0.55f64, 0.55f64, 0.55f64];
+        let config = WaveformConfig::new(
+            -1f64,
+            1f64,
+            Color::Scalar(0),
+            Color::Scalar(255),
+            ).unwrap();
+        let mut wfr = BinnedWaveformRenderer::new(
+            &SampleSequence {
+                data: &samples[..],
+                sample_rate: 44100f64,
+            },
+            1,
+            config,
+        ).unwrap();
+
+        wfr.set_antialiasing(true);
+        assert!(wfr.get_antialiasing());
+
+        let v = wfr.render_vec(tr, (width, height)).unwrap();
+
+        // Somewhere there should be a row that is neither pure background
+        // nor pure foreground, proving the boundary got blended.
+        assert!(v.iter().any(|b| *b != 0 && *b != 255));
+    }
+
+    #[test]
+    fn gradient_colors_columns_by_peak_amplitude() {
+        let tr = TimeRange::Samples(0, 2000);
+        let (width, height) = (2, 100);
+        let mut samples: Vec<f64> = Vec::new();
+        for t in 0u32..1000u32 {
+            samples.push(0.05f64 * ((t as f64) * 0.1f64).sin());
+        }
+        for t in 0u32..1000u32 {
+            samples.push(0.95f64 * ((t as f64) * 0.1f64).sin());
+        }
+        samples.extend(vec![0f64; 1000]); // padding bin, outside `tr`
+
+        let config = WaveformConfig::new(-1f64, 1f64, Color::Vector3(0, 0, 0), Color::Vector3(255, 255, 255)).unwrap();
+        let mut wfr = BinnedWaveformRenderer::new(
+            &SampleSequence { data: &samples[..], sample_rate: 44100f64 },
+            1000,
+            config,
+        ).unwrap();
+
+        wfr.set_gradient(&[Color::Vector3(0, 0, 255), Color::Vector3(255, 0, 0)]).unwrap();
+
+        let v = wfr.render_vec(tr, (width, height)).unwrap();
+
+        // The loud column (x=1) should contain more red than the quiet
+        // column (x=0) somewhere in its foreground rows.
+        let col_red = |x: usize| -> u8 {
+            (0..height).map(|y| v[(y * width + x) * 3]).max().unwrap()
+        };
+        assert!(col_red(1) > col_red(0));
+    }
+
+    #[test]
+    #[cfg(feature = "ref-renderer")]
+    fn renderer_ref_matches_owned_renderer() {
+        let bins = [
+            MinMaxPair { min: -1f64, max: -1f64 },
+            MinMaxPair { min: 1f64, max: 1f64 },
+            MinMaxPair { min: 0f64, max: 0f64 },
+        ];
+        let config_owned = WaveformConfig::new(-1f64, 1f64, Color::Scalar(0), Color::Scalar(255)).unwrap();
+        let config_ref = WaveformConfig::new(-1f64, 1f64, Color::Scalar(0), Color::Scalar(255)).unwrap();
+
+        let samples: Vec<f64> = vec![-1f64, 1f64, 0f64];
+        let owned = BinnedWaveformRenderer::new(
+            &SampleSequence { data: &samples[..], sample_rate: 1f64 },
+            1,
+            config_owned,
+        ).unwrap();
+        let by_ref = BinnedWaveformRendererRef::new(&bins, 1, 1f64, config_ref).unwrap();
+
+        let tr = TimeRange::Samples(0, 2);
+        let (width, height) = (2, 10);
+
+        let v_owned = owned.render_vec(tr, (width, height)).unwrap();
+
+        let mut v_ref = vec![0u8; width * height];
+        by_ref.render_write(tr, (0, 0), (width, height), &mut v_ref[..], (width, height)).unwrap();
+
+        assert_eq!(v_owned, v_ref);
+    }
+
     #[test]
     fn markers() {
         let c = Color::Scalar(0);
@@ -0,0 +1,311 @@
+//! Animated GIF rendering of a scrolling time window.
+//!
+//! Like `png`, this only consumes `BinnedWaveformRenderer`'s public
+//! `render_vec` surface: because the bin/max data is computed once up
+//! front in `BinnedWaveformRenderer::new`, each frame of the animation is
+//! just another (cheap) `render_vec` call over a shifted `TimeRange`.
+
+use std::collections::HashMap;
+
+use binned::BinnedWaveformRenderer;
+use misc::*;
+
+const GIF_HEADER: &[u8] = b"GIF89a";
+
+/// Number of distinct colors in the palette every frame is quantized to.
+///
+/// 256 lets every frame use a full byte per pixel index, which keeps the
+/// quantization and the LZW encoder below simple: there's no need to pick
+/// a tighter code size per-animation.
+const PALETTE_SIZE: usize = 256;
+
+impl<T: Sample> BinnedWaveformRenderer<T> {
+    /// Renders an animated GIF of `nb_frames` frames, each one `render_vec`
+    /// over `window` shifted forward by `step` (in the same units as
+    /// `window`, i.e. seconds for `TimeRange::Seconds` or samples for
+    /// `TimeRange::Samples`).
+    ///
+    /// `None` will be returned if the area of `shape` is zero or if
+    /// `nb_frames` is zero.
+    ///
+    /// # Arguments
+    ///
+    /// * `window` - The `TimeRange` of the first frame; later frames are
+    ///              this range shifted by `step`.
+    /// * `shape` - The `(width, height)` of each frame in pixels.
+    /// * `nb_frames` - How many frames to render.
+    /// * `step` - How far to shift `window` forward for each subsequent
+    ///            frame, in the same units as `window`.
+    /// * `delay_cs` - The delay between frames, in hundredths of a second,
+    ///                as stored in the GIF graphics control extension.
+    pub fn render_gif(&self, window: TimeRange, shape: (usize, usize), nb_frames: usize, step: f64, delay_cs: u16) -> Option<Vec<u8>> {
+        let (w, h) = shape;
+        if w == 0 || h == 0 || nb_frames == 0 {
+            return None;
+        }
+
+        let frames: Vec<Vec<u8>> = (0..nb_frames)
+            .map(|i| self.render_vec(shift_range(window, step * (i as f64)), shape))
+            .collect::<Option<Vec<Vec<u8>>>>()?;
+
+        let channels = match self.config.get_background() {
+            Color::Scalar(_) => 1,
+            Color::Vector3 { .. } => 3,
+            Color::Vector4 { .. } => 4,
+        };
+
+        let palette = fixed_palette();
+        let indexed_frames: Vec<Vec<u8>> = frames
+            .iter()
+            .map(|frame| quantize_frame(frame, channels))
+            .collect();
+
+        let mut out = Vec::new();
+        out.extend_from_slice(GIF_HEADER);
+        write_logical_screen_descriptor(&mut out, w as u16, h as u16);
+        write_global_color_table(&mut out, &palette);
+        write_netscape_loop_extension(&mut out);
+
+        for frame in &indexed_frames {
+            write_graphics_control_extension(&mut out, delay_cs);
+            write_image_descriptor(&mut out, w as u16, h as u16);
+            write_image_data(&mut out, frame);
+        }
+
+        out.push(0x3b); // trailer
+
+        Some(out)
+    }
+}
+
+fn shift_range(range: TimeRange, offset: f64) -> TimeRange {
+    match range {
+        TimeRange::Seconds(b, e) => TimeRange::Seconds(b + offset, e + offset),
+        TimeRange::Samples(b, e) => TimeRange::Samples(
+            (b as f64 + offset).max(0f64) as usize,
+            (e as f64 + offset).max(0f64) as usize,
+        ),
+    }
+}
+
+/// A fixed RGB 3-3-2 color cube: 8 levels of red, 8 of green, 4 of blue,
+/// for exactly 256 entries. Using a fixed palette instead of an adaptive
+/// one (e.g. median-cut) keeps this encoder simple; waveform frames are
+/// already close to two-tone, so the loss of precision is minor.
+fn fixed_palette() -> Vec<[u8; 3]> {
+    let mut palette = Vec::with_capacity(PALETTE_SIZE);
+    for r in 0..8u32 {
+        for g in 0..8u32 {
+            for b in 0..4u32 {
+                palette.push([
+                    ((r * 255) / 7) as u8,
+                    ((g * 255) / 7) as u8,
+                    ((b * 255) / 3) as u8,
+                ]);
+            }
+        }
+    }
+    palette
+}
+
+fn palette_index(r: u8, g: u8, b: u8) -> u8 {
+    let ri = u32::from(r) >> 5;
+    let gi = u32::from(g) >> 5;
+    let bi = u32::from(b) >> 6;
+    ((ri * 8 + gi) * 4 + bi) as u8
+}
+
+fn quantize_frame(frame: &[u8], channels: usize) -> Vec<u8> {
+    frame
+        .chunks(channels)
+        .map(|px| {
+            let (r, g, b) = match channels {
+                1 => (px[0], px[0], px[0]),
+                3 | 4 => (px[0], px[1], px[2]),
+                _ => unreachable!(),
+            };
+            palette_index(r, g, b)
+        })
+        .collect()
+}
+
+fn write_logical_screen_descriptor(out: &mut Vec<u8>, width: u16, height: u16) {
+    out.extend_from_slice(&width.to_le_bytes());
+    out.extend_from_slice(&height.to_le_bytes());
+
+    // Global color table present, color resolution 7, not sorted,
+    // global color table size = 2^(7+1) = 256 entries.
+    out.push(0b1111_0111);
+    out.push(0); // background color index
+    out.push(0); // pixel aspect ratio
+}
+
+fn write_global_color_table(out: &mut Vec<u8>, palette: &[[u8; 3]]) {
+    for color in palette {
+        out.extend_from_slice(color);
+    }
+}
+
+/// The de-facto standard (if non-official) "loop forever" extension, so
+/// preview loops generated by `render_gif` actually loop.
+fn write_netscape_loop_extension(out: &mut Vec<u8>) {
+    out.push(0x21); // extension introducer
+    out.push(0xff); // application extension label
+    out.push(11); // block size
+    out.extend_from_slice(b"NETSCAPE2.0");
+    out.push(3); // sub-block size
+    out.push(1); // sub-block id
+    out.extend_from_slice(&0u16.to_le_bytes()); // loop count, 0 = forever
+    out.push(0); // block terminator
+}
+
+fn write_graphics_control_extension(out: &mut Vec<u8>, delay_cs: u16) {
+    out.push(0x21); // extension introducer
+    out.push(0xf9); // graphic control label
+    out.push(4); // block size
+    out.push(0); // no transparency, no disposal preference
+    out.extend_from_slice(&delay_cs.to_le_bytes());
+    out.push(0); // transparent color index (unused)
+    out.push(0); // block terminator
+}
+
+fn write_image_descriptor(out: &mut Vec<u8>, width: u16, height: u16) {
+    out.push(0x2c); // image separator
+    out.extend_from_slice(&0u16.to_le_bytes()); // left
+    out.extend_from_slice(&0u16.to_le_bytes()); // top
+    out.extend_from_slice(&width.to_le_bytes());
+    out.extend_from_slice(&height.to_le_bytes());
+    out.push(0); // no local color table, not interlaced
+}
+
+fn write_image_data(out: &mut Vec<u8>, indices: &[u8]) {
+    let min_code_size = 8u8;
+    out.push(min_code_size);
+
+    let lzw = lzw_encode(indices, min_code_size);
+    for chunk in lzw.chunks(255) {
+        out.push(chunk.len() as u8);
+        out.extend_from_slice(chunk);
+    }
+    out.push(0); // block terminator
+}
+
+/// A standard variable-width GIF LZW encoder, bit-packed least-significant
+/// bit first as required by the format.
+fn lzw_encode(data: &[u8], min_code_size: u8) -> Vec<u8> {
+    let clear_code: u32 = 1 << min_code_size;
+    let end_code: u32 = clear_code + 1;
+
+    let mut bits = BitWriter::new();
+    let mut code_size = u32::from(min_code_size) + 1;
+    let mut next_code = end_code + 1;
+    let mut table: HashMap<Vec<u8>, u32> = HashMap::new();
+
+    let reset_table = |table: &mut HashMap<Vec<u8>, u32>| {
+        table.clear();
+        for i in 0..clear_code {
+            table.insert(vec![i as u8], i);
+        }
+    };
+    reset_table(&mut table);
+
+    bits.write_code(clear_code, code_size);
+
+    let mut current: Vec<u8> = Vec::new();
+    for &byte in data {
+        let mut extended = current.clone();
+        extended.push(byte);
+
+        if table.contains_key(&extended) {
+            current = extended;
+        } else {
+            bits.write_code(table[&current], code_size);
+
+            table.insert(extended, next_code);
+            next_code += 1;
+            if next_code >= (1 << code_size) && code_size < 12 {
+                code_size += 1;
+            } else if next_code > 4096 {
+                bits.write_code(clear_code, code_size);
+                reset_table(&mut table);
+                code_size = u32::from(min_code_size) + 1;
+                next_code = end_code + 1;
+            }
+
+            current = vec![byte];
+        }
+    }
+
+    if !current.is_empty() {
+        bits.write_code(table[&current], code_size);
+    }
+    bits.write_code(end_code, code_size);
+    bits.finish()
+}
+
+struct BitWriter {
+    out: Vec<u8>,
+    acc: u32,
+    nbits: u32,
+}
+
+impl BitWriter {
+    fn new() -> Self {
+        BitWriter { out: Vec::new(), acc: 0, nbits: 0 }
+    }
+
+    fn write_code(&mut self, code: u32, code_size: u32) {
+        self.acc |= code << self.nbits;
+        self.nbits += code_size;
+        while self.nbits >= 8 {
+            self.out.push((self.acc & 0xff) as u8);
+            self.acc >>= 8;
+            self.nbits -= 8;
+        }
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        if self.nbits > 0 {
+            self.out.push((self.acc & 0xff) as u8);
+        }
+        self.out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ::misc::*;
+
+    #[test]
+    fn render_gif_has_header_and_trailer() {
+        let window = TimeRange::Samples(0, 100);
+        let (width, height) = (10, 8);
+        let samples: Vec<f64> = (0..200).map(|i| ((i as f64) * 0.1).sin()).collect();
+        let config = WaveformConfig::new(-1f64, 1f64, Color::Vector3(0, 0, 0), Color::Vector3(255, 255, 255)).unwrap();
+        let wfr = BinnedWaveformRenderer::new(
+            &SampleSequence { data: &samples[..], sample_rate: 44100f64 },
+            10,
+            config,
+        ).unwrap();
+
+        let gif = wfr.render_gif(window, (width, height), 3, 10f64, 10).unwrap();
+
+        assert_eq!(&gif[0..6], GIF_HEADER);
+        assert_eq!(*gif.last().unwrap(), 0x3b);
+    }
+
+    #[test]
+    fn render_gif_rejects_zero_frames() {
+        let window = TimeRange::Samples(0, 100);
+        let samples: Vec<f64> = vec![0f64; 200];
+        let config = WaveformConfig::new(-1f64, 1f64, Color::Vector3(0, 0, 0), Color::Vector3(255, 255, 255)).unwrap();
+        let wfr = BinnedWaveformRenderer::new(
+            &SampleSequence { data: &samples[..], sample_rate: 44100f64 },
+            10,
+            config,
+        ).unwrap();
+
+        assert!(wfr.render_gif(window, (10, 8), 0, 10f64, 10).is_none());
+    }
+}
@@ -0,0 +1,7 @@
+//! A fast waveform renderer for audio samples.
+
+pub mod error;
+pub mod misc;
+pub mod binned;
+pub mod png;
+pub mod gif;